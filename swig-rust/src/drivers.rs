@@ -0,0 +1,272 @@
+#[cfg(any(feature = "postgres", feature = "sqlite", feature = "mysql"))]
+use crate::error::Error;
+use crate::error::Result;
+use crate::value::Value;
+
+pub mod asyncd;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "mysql")]
+pub mod mysql;
+
+pub use asyncd::{AsyncDriver, AsyncTransaction, BlockingAdapter};
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresDriver;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteDriver;
+#[cfg(feature = "mysql")]
+pub use mysql::MySqlDriver;
+
+/// Checks that `name` is safe to splice directly into a `SAVEPOINT`/
+/// `ROLLBACK TO SAVEPOINT`/`RELEASE SAVEPOINT` statement.
+///
+/// Every backend builds these statements with `format!` rather than a bound
+/// parameter, since savepoint names aren't data the driver crates accept as
+/// bind args - so a name containing whitespace, quotes, or a statement
+/// separator would break the statement or inject SQL. Restricting names to
+/// a plain identifier charset closes that off without needing per-backend
+/// quoting rules.
+#[cfg(any(feature = "postgres", feature = "sqlite", feature = "mysql"))]
+pub(crate) fn validate_savepoint_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::Binding(format!(
+            "invalid savepoint name: {name:?}"
+        )))
+    }
+}
+
+pub trait Driver {
+    type Transaction: Transaction;
+
+    fn exec(&self, sql: &str, args: &[&str]) -> Result<()>;
+
+    /// Like [`Driver::exec`], but takes typed [`Value`] args instead of
+    /// pre-stringified `&str`, so integers, bytes, and nulls survive
+    /// unchanged. The default falls back to [`Driver::exec`] by stringifying
+    /// each value; backends should override this to bind natively.
+    fn exec_typed(&self, sql: &str, args: &[Value]) -> Result<()> {
+        let strings: Vec<String> = args.iter().map(Value::to_bind_string).collect();
+        let refs: Vec<&str> = strings.iter().map(String::as_str).collect();
+        self.exec(sql, &refs)
+    }
+
+    /// Opens a new transaction. Callers normally go through [`Driver::transaction`]
+    /// instead of calling this directly.
+    fn begin_transaction(&self) -> Result<Self::Transaction>;
+
+    /// Returns the versions already recorded in `table`, in no particular
+    /// order. Used by [`crate::migration::Runner`] to work out which
+    /// migrations are still pending.
+    fn applied_versions(&self, table: &str) -> Result<Vec<i64>>;
+
+    /// Runs `f` inside a transaction, committing on `Ok` and rolling back on
+    /// `Err`, so a multi-statement migration is applied all-or-nothing.
+    fn transaction<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&Self::Transaction) -> Result<T>,
+    {
+        let tx = self.begin_transaction()?;
+        tx.begin()?;
+
+        match f(&tx) {
+            Ok(value) => {
+                tx.commit()?;
+                Ok(value)
+            }
+            Err(err) => match tx.rollback() {
+                Ok(()) => Err(err),
+                Err(rollback_error) => Err(crate::error::Error::RollbackFailed {
+                    cause: Box::new(err),
+                    rollback_error: Box::new(rollback_error),
+                }),
+            },
+        }
+    }
+}
+
+pub trait Transaction {
+    fn exec(&self, sql: &str, args: &[&str]) -> Result<()>;
+
+    /// Like [`Driver::exec_typed`], for statements run inside the
+    /// transaction.
+    fn exec_typed(&self, sql: &str, args: &[Value]) -> Result<()> {
+        let strings: Vec<String> = args.iter().map(Value::to_bind_string).collect();
+        let refs: Vec<&str> = strings.iter().map(String::as_str).collect();
+        self.exec(sql, &refs)
+    }
+
+    /// Starts the transaction (e.g. issues `BEGIN`).
+    fn begin(&self) -> Result<()>;
+    /// Commits the transaction.
+    fn commit(&self) -> Result<()>;
+    /// Rolls back the transaction in full.
+    fn rollback(&self) -> Result<()>;
+
+    /// Creates a named savepoint inside the current transaction.
+    fn savepoint(&self, name: &str) -> Result<()>;
+    /// Rolls back to a previously created savepoint, without ending the
+    /// outer transaction.
+    fn rollback_to(&self, name: &str) -> Result<()>;
+    /// Releases a savepoint, discarding it without rolling back.
+    fn release(&self, name: &str) -> Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use crate::error::Error;
+
+    use super::*;
+
+    /// A [`Driver`]/[`Transaction`] pair whose behavior is scripted by the
+    /// test, so `Driver::transaction`'s commit/rollback branching can be
+    /// exercised without a real database.
+    #[derive(Default)]
+    struct FakeDriver {
+        rollback_fails: bool,
+        rolled_back: Rc<Cell<bool>>,
+        committed: Rc<Cell<bool>>,
+    }
+
+    struct FakeTransaction {
+        rollback_fails: bool,
+        rolled_back: Rc<Cell<bool>>,
+        committed: Rc<Cell<bool>>,
+    }
+
+    impl Driver for FakeDriver {
+        type Transaction = FakeTransaction;
+
+        fn exec(&self, _sql: &str, _args: &[&str]) -> Result<()> {
+            Ok(())
+        }
+
+        fn begin_transaction(&self) -> Result<Self::Transaction> {
+            Ok(FakeTransaction {
+                rollback_fails: self.rollback_fails,
+                rolled_back: self.rolled_back.clone(),
+                committed: self.committed.clone(),
+            })
+        }
+
+        fn applied_versions(&self, _table: &str) -> Result<Vec<i64>> {
+            Ok(vec![])
+        }
+    }
+
+    impl Transaction for FakeTransaction {
+        fn exec(&self, _sql: &str, _args: &[&str]) -> Result<()> {
+            Ok(())
+        }
+
+        fn begin(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn commit(&self) -> Result<()> {
+            self.committed.set(true);
+            Ok(())
+        }
+
+        fn rollback(&self) -> Result<()> {
+            if self.rollback_fails {
+                return Err(Error::Driver("connection reset".into()));
+            }
+            self.rolled_back.set(true);
+            Ok(())
+        }
+
+        fn savepoint(&self, _name: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn rollback_to(&self, _name: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn release(&self, _name: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn transaction_commits_on_ok() {
+        let driver = FakeDriver {
+            rollback_fails: false,
+            ..Default::default()
+        };
+
+        let result = driver.transaction(|_tx| Ok(42));
+
+        assert_eq!(result.unwrap(), 42);
+        assert!(driver.committed.get());
+        assert!(!driver.rolled_back.get());
+    }
+
+    #[test]
+    fn transaction_rolls_back_and_preserves_error_on_err() {
+        let driver = FakeDriver {
+            rollback_fails: false,
+            ..Default::default()
+        };
+
+        let result: Result<()> = driver.transaction(|_tx| Err(Error::Driver("boom".into())));
+
+        match result {
+            Err(Error::Driver(msg)) => assert_eq!(msg, "boom"),
+            other => panic!("expected the closure's own error, got {other:?}"),
+        }
+        assert!(driver.rolled_back.get());
+        assert!(!driver.committed.get());
+    }
+
+    #[test]
+    fn transaction_keeps_original_error_when_rollback_also_fails() {
+        let driver = FakeDriver {
+            rollback_fails: true,
+            ..Default::default()
+        };
+
+        let result: Result<()> = driver.transaction(|_tx| Err(Error::Driver("boom".into())));
+
+        match result {
+            Err(Error::RollbackFailed {
+                cause,
+                rollback_error,
+            }) => {
+                assert!(matches!(*cause, Error::Driver(ref m) if m == "boom"));
+                assert!(
+                    matches!(*rollback_error, Error::Driver(ref m) if m == "connection reset")
+                );
+            }
+            other => panic!("expected RollbackFailed, got {other:?}"),
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "postgres", feature = "sqlite", feature = "mysql"))]
+    fn validate_savepoint_name_accepts_plain_identifiers() {
+        assert!(validate_savepoint_name("sp1").is_ok());
+        assert!(validate_savepoint_name("_sp_1").is_ok());
+    }
+
+    #[test]
+    #[cfg(any(feature = "postgres", feature = "sqlite", feature = "mysql"))]
+    fn validate_savepoint_name_rejects_anything_that_could_escape_the_statement() {
+        assert!(validate_savepoint_name("").is_err());
+        assert!(validate_savepoint_name("1sp").is_err());
+        assert!(validate_savepoint_name("sp; DROP TABLE users").is_err());
+        assert!(validate_savepoint_name("sp name").is_err());
+        assert!(validate_savepoint_name("sp'").is_err());
+    }
+}
@@ -0,0 +1,190 @@
+use std::sync::{Arc, Mutex};
+
+use mysql::prelude::Queryable;
+use mysql::{Pool, PooledConn};
+
+use crate::error::{Error, Result};
+use crate::value::Value;
+
+use super::{validate_savepoint_name, Driver, Transaction};
+
+fn in_use_err() -> Error {
+    Error::Driver("mysql connection is in use by an open transaction".into())
+}
+
+/// A [`Driver`] backed by a [`mysql::Pool`].
+///
+/// The connection is wrapped in `Arc<Mutex<Option<_>>>` rather than
+/// `Arc<Mutex<_>>`: a transaction takes the connection out of the `Option`
+/// for its entire lifetime (see [`MySqlTransaction`]) instead of the driver
+/// re-locking and releasing the mutex per statement, so a concurrent
+/// `exec`/`applied_versions`/second transaction on the same driver can't
+/// interleave its own statements onto a transaction that's still open.
+pub struct MySqlDriver {
+    conn: Arc<Mutex<Option<PooledConn>>>,
+}
+
+impl MySqlDriver {
+    pub fn new(pool: &Pool) -> Result<Self> {
+        let conn = pool.get_conn().map_err(|e| Error::Driver(e.to_string()))?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(Some(conn))),
+        })
+    }
+}
+
+impl Driver for MySqlDriver {
+    type Transaction = MySqlTransaction;
+
+    fn exec(&self, sql: &str, args: &[&str]) -> Result<()> {
+        let mut guard = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Driver("mysql connection poisoned".into()))?;
+        let conn = guard.as_mut().ok_or_else(in_use_err)?;
+
+        let params: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+
+        conn.exec_drop(sql, params)
+            .map_err(|e| Error::Driver(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn exec_typed(&self, sql: &str, args: &[Value]) -> Result<()> {
+        let mut guard = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Driver("mysql connection poisoned".into()))?;
+        let conn = guard.as_mut().ok_or_else(in_use_err)?;
+
+        let params: Vec<mysql::Value> = args.iter().cloned().map(mysql::Value::from).collect();
+
+        conn.exec_drop(sql, params)
+            .map_err(|e| Error::Driver(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn begin_transaction(&self) -> Result<Self::Transaction> {
+        let mut guard = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Driver("mysql connection poisoned".into()))?;
+        let conn = guard.take().ok_or_else(in_use_err)?;
+
+        Ok(MySqlTransaction {
+            conn: Mutex::new(Some(conn)),
+            shared: self.conn.clone(),
+        })
+    }
+
+    fn applied_versions(&self, table: &str) -> Result<Vec<i64>> {
+        let mut guard = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Driver("mysql connection poisoned".into()))?;
+        let conn = guard.as_mut().ok_or_else(in_use_err)?;
+
+        conn.query(format!("SELECT version FROM {table}"))
+            .map_err(|e| Error::Driver(e.to_string()))
+    }
+}
+
+/// A transaction on a [`MySqlDriver`]'s connection. The connection is held
+/// here exclusively (taken out of the driver's shared slot by
+/// `begin_transaction`) for as long as the transaction is alive, and handed
+/// back to the driver when it's dropped. It's kept behind its own `Mutex`
+/// (rather than a plain field) purely so `Transaction`'s `&self` methods can
+/// get `&mut PooledConn` for `exec_drop`; there's never contention on it
+/// since nothing else holds a reference to this transaction's connection.
+pub struct MySqlTransaction {
+    conn: Mutex<Option<PooledConn>>,
+    shared: Arc<Mutex<Option<PooledConn>>>,
+}
+
+impl MySqlTransaction {
+    fn exec_raw(&self, sql: &str) -> Result<()> {
+        let mut guard = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Driver("mysql connection poisoned".into()))?;
+        let conn = guard.as_mut().expect("connection taken before drop");
+
+        conn.exec_drop(sql, ())
+            .map_err(|e| Error::Driver(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+impl Drop for MySqlTransaction {
+    fn drop(&mut self) {
+        if let Ok(mut conn_guard) = self.conn.lock() {
+            if let Some(conn) = conn_guard.take() {
+                if let Ok(mut shared_guard) = self.shared.lock() {
+                    *shared_guard = Some(conn);
+                }
+            }
+        }
+    }
+}
+
+impl Transaction for MySqlTransaction {
+    fn exec(&self, sql: &str, args: &[&str]) -> Result<()> {
+        let mut guard = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Driver("mysql connection poisoned".into()))?;
+        let conn = guard.as_mut().expect("connection taken before drop");
+
+        let params: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+
+        conn.exec_drop(sql, params)
+            .map_err(|e| Error::Driver(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn exec_typed(&self, sql: &str, args: &[Value]) -> Result<()> {
+        let mut guard = self
+            .conn
+            .lock()
+            .map_err(|_| Error::Driver("mysql connection poisoned".into()))?;
+        let conn = guard.as_mut().expect("connection taken before drop");
+
+        let params: Vec<mysql::Value> = args.iter().cloned().map(mysql::Value::from).collect();
+
+        conn.exec_drop(sql, params)
+            .map_err(|e| Error::Driver(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn begin(&self) -> Result<()> {
+        self.exec_raw("BEGIN")
+    }
+
+    fn commit(&self) -> Result<()> {
+        self.exec_raw("COMMIT")
+    }
+
+    fn rollback(&self) -> Result<()> {
+        self.exec_raw("ROLLBACK")
+    }
+
+    fn savepoint(&self, name: &str) -> Result<()> {
+        validate_savepoint_name(name)?;
+        self.exec_raw(&format!("SAVEPOINT {name}"))
+    }
+
+    fn rollback_to(&self, name: &str) -> Result<()> {
+        validate_savepoint_name(name)?;
+        self.exec_raw(&format!("ROLLBACK TO SAVEPOINT {name}"))
+    }
+
+    fn release(&self, name: &str) -> Result<()> {
+        validate_savepoint_name(name)?;
+        self.exec_raw(&format!("RELEASE SAVEPOINT {name}"))
+    }
+}
@@ -0,0 +1,300 @@
+use std::sync::{Arc, Mutex};
+
+use bytes::BytesMut;
+use tokio_postgres::types::{to_sql_checked, IsNull, ToSql, Type};
+use tokio_postgres::Client;
+
+use crate::error::{Error, Result};
+use crate::value::Value;
+
+use super::{validate_savepoint_name, Driver, Transaction};
+
+/// A SQL `NULL` that can be bound into a column of any type.
+///
+/// `tokio_postgres` validates a bound value's Rust type against the
+/// server-reported column type via `ToSql::accepts`. Boxing `Value::Null` as
+/// `Option::<i64>::None` (as every other variant boxes its native Rust type)
+/// would make `accepts` only match `INT8`, so binding a null into any other
+/// column type would fail. `AnyNull` accepts every type and always writes
+/// `IsNull::Yes`, since a null carries no type information of its own to
+/// validate.
+#[derive(Debug)]
+struct AnyNull;
+
+impl ToSql for AnyNull {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        _out: &mut BytesMut,
+    ) -> std::result::Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(IsNull::Yes)
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+/// Rewrites `?`-style placeholders (the convention the rest of the crate
+/// writes typed SQL in, matching sqlite/mysql's native syntax) into
+/// Postgres's positional `$1`, `$2`, ... form.
+///
+/// Tracks whether it's inside a single-quoted string literal (honoring the
+/// `''`-escape convention) so a literal `?` in migration SQL - e.g. inside a
+/// default value or a `LIKE` pattern - isn't mistaken for a placeholder.
+/// This doesn't (and can't, without a real SQL parser) distinguish a bind
+/// placeholder from Postgres's jsonb `?` containment operator; SQL that
+/// uses that operator needs to go through `Driver::exec` with its own
+/// literal `$`-style binding instead of `exec_typed`.
+fn rewrite_placeholders(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut n = 0;
+    let mut in_string = false;
+    for c in sql.chars() {
+        match c {
+            '\'' => {
+                in_string = !in_string;
+                out.push(c);
+            }
+            '?' if !in_string => {
+                n += 1;
+                out.push('$');
+                out.push_str(&n.to_string());
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Maps a [`Value`] onto a boxed [`ToSql`] so it can be passed to
+/// `tokio_postgres` alongside the `&[&str]`-based `exec` args.
+fn to_postgres_param(value: &Value) -> Box<dyn ToSql + Sync + '_> {
+    match value {
+        Value::Null => Box::new(AnyNull),
+        Value::Int(i) => Box::new(*i),
+        Value::Float(f) => Box::new(*f),
+        Value::Text(s) => Box::new(s.as_str()),
+        Value::Bytes(b) => Box::new(b.as_slice()),
+        Value::Bool(b) => Box::new(*b),
+    }
+}
+
+fn in_use_err() -> Error {
+    Error::Driver("postgres connection is in use by an open transaction".into())
+}
+
+/// A [`Driver`] backed by a [`tokio_postgres::Client`].
+///
+/// `exec` is synchronous from the caller's point of view: it blocks on the
+/// Tokio runtime handle passed to [`PostgresDriver::new`] for the duration
+/// of the query. Async callers
+/// should prefer [`crate::drivers::AsyncDriver`] instead.
+///
+/// The client is wrapped in `Arc<Mutex<Option<_>>>` rather than
+/// `Arc<Mutex<_>>`: a transaction takes the client out of the `Option` for
+/// its entire lifetime (see [`PostgresTransaction`]) instead of the driver
+/// re-locking and releasing the mutex per statement, so a concurrent
+/// `exec`/`applied_versions`/second transaction on the same driver can't
+/// interleave its own statements onto a transaction that's still open.
+pub struct PostgresDriver {
+    client: Arc<Mutex<Option<Client>>>,
+    handle: tokio::runtime::Handle,
+}
+
+impl PostgresDriver {
+    pub fn new(client: Client, handle: tokio::runtime::Handle) -> Self {
+        Self {
+            client: Arc::new(Mutex::new(Some(client))),
+            handle,
+        }
+    }
+}
+
+impl Driver for PostgresDriver {
+    type Transaction = PostgresTransaction;
+
+    fn exec(&self, sql: &str, args: &[&str]) -> Result<()> {
+        let guard = self
+            .client
+            .lock()
+            .map_err(|_| Error::Driver("postgres connection poisoned".into()))?;
+        let client = guard.as_ref().ok_or_else(in_use_err)?;
+
+        let params: Vec<&(dyn ToSql + Sync)> =
+            args.iter().map(|a| a as &(dyn ToSql + Sync)).collect();
+
+        self.handle
+            .block_on(client.execute(sql, &params))
+            .map_err(|e| Error::Driver(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn exec_typed(&self, sql: &str, args: &[Value]) -> Result<()> {
+        let guard = self
+            .client
+            .lock()
+            .map_err(|_| Error::Driver("postgres connection poisoned".into()))?;
+        let client = guard.as_ref().ok_or_else(in_use_err)?;
+
+        let owned: Vec<Box<dyn ToSql + Sync + '_>> = args.iter().map(to_postgres_param).collect();
+        let params: Vec<&(dyn ToSql + Sync)> = owned.iter().map(|p| p.as_ref()).collect();
+
+        self.handle
+            .block_on(client.execute(&rewrite_placeholders(sql), &params))
+            .map_err(|e| Error::Driver(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn begin_transaction(&self) -> Result<Self::Transaction> {
+        let mut guard = self
+            .client
+            .lock()
+            .map_err(|_| Error::Driver("postgres connection poisoned".into()))?;
+        let client = guard.take().ok_or_else(in_use_err)?;
+
+        Ok(PostgresTransaction {
+            client: Some(client),
+            shared: self.client.clone(),
+            handle: self.handle.clone(),
+        })
+    }
+
+    fn applied_versions(&self, table: &str) -> Result<Vec<i64>> {
+        let guard = self
+            .client
+            .lock()
+            .map_err(|_| Error::Driver("postgres connection poisoned".into()))?;
+        let client = guard.as_ref().ok_or_else(in_use_err)?;
+
+        let rows = self
+            .handle
+            .block_on(client.query(&format!("SELECT version FROM {table}"), &[]))
+            .map_err(|e| Error::Driver(e.to_string()))?;
+
+        Ok(rows.iter().map(|row| row.get::<_, i64>(0)).collect())
+    }
+}
+
+/// A transaction on a [`PostgresDriver`]'s connection.
+///
+/// `BEGIN`/`COMMIT`/`ROLLBACK`/`SAVEPOINT` are issued as plain statements
+/// over the connection rather than via `tokio_postgres::Transaction`. The
+/// client is held here exclusively (taken out of the driver's shared slot by
+/// `begin_transaction`) for as long as the transaction is alive, and handed
+/// back to the driver when it's dropped.
+pub struct PostgresTransaction {
+    client: Option<Client>,
+    shared: Arc<Mutex<Option<Client>>>,
+    handle: tokio::runtime::Handle,
+}
+
+impl PostgresTransaction {
+    fn exec(&self, sql: &str) -> Result<()> {
+        let client = self.client.as_ref().expect("client taken before drop");
+
+        self.handle
+            .block_on(client.execute(sql, &[]))
+            .map_err(|e| Error::Driver(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+impl Drop for PostgresTransaction {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            if let Ok(mut guard) = self.shared.lock() {
+                *guard = Some(client);
+            }
+        }
+    }
+}
+
+impl Transaction for PostgresTransaction {
+    fn exec(&self, sql: &str, args: &[&str]) -> Result<()> {
+        let client = self.client.as_ref().expect("client taken before drop");
+
+        let params: Vec<&(dyn ToSql + Sync)> =
+            args.iter().map(|a| a as &(dyn ToSql + Sync)).collect();
+
+        self.handle
+            .block_on(client.execute(sql, &params))
+            .map_err(|e| Error::Driver(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn exec_typed(&self, sql: &str, args: &[Value]) -> Result<()> {
+        let client = self.client.as_ref().expect("client taken before drop");
+
+        let owned: Vec<Box<dyn ToSql + Sync + '_>> = args.iter().map(to_postgres_param).collect();
+        let params: Vec<&(dyn ToSql + Sync)> = owned.iter().map(|p| p.as_ref()).collect();
+
+        self.handle
+            .block_on(client.execute(&rewrite_placeholders(sql), &params))
+            .map_err(|e| Error::Driver(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn begin(&self) -> Result<()> {
+        PostgresTransaction::exec(self, "BEGIN")
+    }
+
+    fn commit(&self) -> Result<()> {
+        PostgresTransaction::exec(self, "COMMIT")
+    }
+
+    fn rollback(&self) -> Result<()> {
+        PostgresTransaction::exec(self, "ROLLBACK")
+    }
+
+    fn savepoint(&self, name: &str) -> Result<()> {
+        validate_savepoint_name(name)?;
+        PostgresTransaction::exec(self, &format!("SAVEPOINT {name}"))
+    }
+
+    fn rollback_to(&self, name: &str) -> Result<()> {
+        validate_savepoint_name(name)?;
+        PostgresTransaction::exec(self, &format!("ROLLBACK TO SAVEPOINT {name}"))
+    }
+
+    fn release(&self, name: &str) -> Result<()> {
+        validate_savepoint_name(name)?;
+        PostgresTransaction::exec(self, &format!("RELEASE SAVEPOINT {name}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrite_placeholders_numbers_each_question_mark_in_order() {
+        assert_eq!(
+            rewrite_placeholders("INSERT INTO t (a, b) VALUES (?, ?)"),
+            "INSERT INTO t (a, b) VALUES ($1, $2)"
+        );
+    }
+
+    #[test]
+    fn rewrite_placeholders_ignores_question_marks_inside_string_literals() {
+        assert_eq!(
+            rewrite_placeholders("SELECT ? FROM t WHERE note = 'what?' OR id = ?"),
+            "SELECT $1 FROM t WHERE note = 'what?' OR id = $2"
+        );
+    }
+
+    #[test]
+    fn rewrite_placeholders_handles_escaped_quotes_in_literals() {
+        assert_eq!(
+            rewrite_placeholders("SELECT ? FROM t WHERE note = 'it''s a ? test'"),
+            "SELECT $1 FROM t WHERE note = 'it''s a ? test'"
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+
+/// Crate-wide result alias used by every `Driver` and `Transaction` implementor.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors that can surface while executing SQL against a backend.
+#[derive(Debug)]
+pub enum Error {
+    /// The underlying database client returned an error.
+    Driver(String),
+    /// A value couldn't be bound into a statement, e.g. a savepoint name
+    /// that isn't a plain identifier.
+    Binding(String),
+    /// A transaction's closure failed, and then rolling it back also
+    /// failed. Both causes are kept: the closure's error is usually the one
+    /// the caller cares about, but a failed rollback means the transaction's
+    /// state is no longer certain.
+    RollbackFailed {
+        cause: Box<Error>,
+        rollback_error: Box<Error>,
+    },
+    /// A migration (or rollback) failed, and then releasing the migration
+    /// lock also failed. Both causes are kept: the original failure is
+    /// usually the one the caller cares about, but a failed release means
+    /// the lock may still be held and block future runs.
+    LockReleaseFailed {
+        cause: Box<Error>,
+        release_error: Box<Error>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Driver(msg) => write!(f, "driver error: {msg}"),
+            Error::Binding(msg) => write!(f, "binding error: {msg}"),
+            Error::RollbackFailed {
+                cause,
+                rollback_error,
+            } => write!(
+                f,
+                "{cause} (rollback also failed: {rollback_error})"
+            ),
+            Error::LockReleaseFailed {
+                cause,
+                release_error,
+            } => write!(
+                f,
+                "{cause} (lock release also failed: {release_error})"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
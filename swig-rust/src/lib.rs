@@ -0,0 +1,7 @@
+pub mod drivers;
+pub mod error;
+pub mod migration;
+pub mod value;
+
+pub use error::{Error, Result};
+pub use value::Value;
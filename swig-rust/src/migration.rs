@@ -0,0 +1,561 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::drivers::{Driver, Transaction};
+use crate::error::{Error, Result};
+use crate::value::Value;
+
+const TRACKING_TABLE: &str = "_swig_migrations";
+const LOCK_TABLE: &str = "_swig_migrations_lock";
+/// Fixed id of the lock table's single row. The table's primary key, so the
+/// first `INSERT` (from whichever runner gets there first) is the only one
+/// that can ever succeed — unlike keying the row on the expiry itself, which
+/// lets every runner's distinct expiry insert as its own row.
+const LOCK_ROW_ID: i64 = 1;
+/// How long a held lock is honored before a new runner is allowed to steal
+/// it. Bounds how long a crashed runner can block migrations, at the cost of
+/// a (very unlikely, for any reasonable lease) double-apply if a slow runner
+/// is still working past its lease when another one steals the lock.
+const DEFAULT_LOCK_LEASE_SECS: i64 = 300;
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the Unix epoch")
+        .as_secs() as i64
+}
+
+/// A single versioned migration: a monotonically increasing `version`, a
+/// human-readable `name`, and the SQL to apply (`up`) or revert (`down`) it.
+#[derive(Debug, Clone)]
+pub struct Migration {
+    pub version: i64,
+    pub name: String,
+    pub up: String,
+    pub down: String,
+}
+
+/// Applies a set of [`Migration`]s against a [`Driver`], tracking which
+/// versions have already run in a `_swig_migrations` table it creates on
+/// first use.
+pub struct Runner<'d, D: Driver> {
+    driver: &'d D,
+    migrations: Vec<Migration>,
+    lock_lease_secs: i64,
+}
+
+impl<'d, D: Driver> Runner<'d, D> {
+    /// Creates a runner over `migrations`, which are applied in ascending
+    /// version order regardless of the order passed in.
+    pub fn new(driver: &'d D, mut migrations: Vec<Migration>) -> Self {
+        migrations.sort_by_key(|m| m.version);
+        Self {
+            driver,
+            migrations,
+            lock_lease_secs: DEFAULT_LOCK_LEASE_SECS,
+        }
+    }
+
+    /// Overrides how long this runner's migration lock is honored before
+    /// another runner is allowed to steal it. See [`DEFAULT_LOCK_LEASE_SECS`].
+    pub fn with_lock_lease_secs(mut self, secs: i64) -> Self {
+        self.lock_lease_secs = secs;
+        self
+    }
+
+    fn ensure_tables(&self) -> Result<()> {
+        self.driver.exec(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS {TRACKING_TABLE} (version BIGINT PRIMARY KEY, name TEXT NOT NULL)"
+            ),
+            &[],
+        )?;
+
+        // `id` is the lock row's fixed identity (always `LOCK_ROW_ID`) so the
+        // primary key serializes acquisition; `version` holds the row's
+        // expiry (unix seconds). Naming the expiry column `version` (rather
+        // than e.g. `expires_at`) lets acquiring/inspecting the lock go
+        // through the same `Driver::applied_versions` query the tracking
+        // table uses, without adding a separate query primitive to `Driver`.
+        self.driver.exec(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS {LOCK_TABLE} (id BIGINT PRIMARY KEY, version BIGINT NOT NULL)"
+            ),
+            &[],
+        )
+    }
+
+    /// Holds the migration lock for the duration of `f`, so two runners
+    /// starting at once don't both try to apply the same migration.
+    ///
+    /// The lock is a single row in `_swig_migrations_lock`, keyed on the
+    /// fixed `LOCK_ROW_ID`: acquiring it is an `INSERT` that fails on the
+    /// primary key if another runner already holds it. Unlike a plain row
+    /// lock, a held lock past its `version` (expiry) column is stale and
+    /// gets stolen via `UPDATE` rather than left to block forever —
+    /// guarding against a runner that crashed mid-migration and never
+    /// reached the `DELETE` below.
+    fn with_lock<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce() -> Result<T>,
+    {
+        self.acquire_lock()?;
+
+        let result = f();
+
+        let release = self.driver.exec_typed(
+            &format!("DELETE FROM {LOCK_TABLE} WHERE id = ?"),
+            &[Value::Int(LOCK_ROW_ID)],
+        );
+
+        match (result, release) {
+            (result, Ok(())) => result,
+            (Ok(_), Err(release_error)) => Err(release_error),
+            (Err(cause), Err(release_error)) => Err(Error::LockReleaseFailed {
+                cause: Box::new(cause),
+                release_error: Box::new(release_error),
+            }),
+        }
+    }
+
+    /// Inserts the lock row, stealing a stale one (past its expiry) via
+    /// `UPDATE` if necessary. The row's id never changes once created, so
+    /// the primary key (not the expiry) is what serializes acquisition
+    /// across concurrent runners.
+    fn acquire_lock(&self) -> Result<()> {
+        let expires_at = now_unix() + self.lock_lease_secs;
+
+        if self.try_insert_lock(expires_at)? {
+            return Ok(());
+        }
+
+        let held_expiry = self
+            .driver
+            .applied_versions(LOCK_TABLE)?
+            .into_iter()
+            .next()
+            .unwrap_or(i64::MIN);
+
+        if now_unix() < held_expiry {
+            return Err(Error::Driver(
+                "migration lock held by another runner".into(),
+            ));
+        }
+
+        self.driver.exec_typed(
+            &format!("UPDATE {LOCK_TABLE} SET version = ? WHERE id = ?"),
+            &[Value::Int(expires_at), Value::Int(LOCK_ROW_ID)],
+        )
+    }
+
+    /// Returns `true` once the `INSERT` succeeds. A failure is assumed to be
+    /// the primary-key collision signaling the lock is already held —
+    /// `Driver`'s `Error` is backend-agnostic, so a genuine connection
+    /// failure here is indistinguishable from that and is treated the same
+    /// way rather than surfaced directly.
+    fn try_insert_lock(&self, expires_at: i64) -> Result<bool> {
+        match self.driver.exec_typed(
+            &format!("INSERT INTO {LOCK_TABLE} (id, version) VALUES (?, ?)"),
+            &[Value::Int(LOCK_ROW_ID), Value::Int(expires_at)],
+        ) {
+            Ok(()) => Ok(true),
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Applies every migration whose version is greater than the highest
+    /// already-recorded version, in ascending order, each inside its own
+    /// transaction alongside the row that records it as applied.
+    pub fn migrate(&self) -> Result<()> {
+        self.ensure_tables()?;
+
+        self.with_lock(|| {
+            let last_applied = self
+                .driver
+                .applied_versions(TRACKING_TABLE)?
+                .into_iter()
+                .max()
+                .unwrap_or(i64::MIN);
+
+            for migration in self.migrations.iter().filter(|m| m.version > last_applied) {
+                self.driver.transaction(|tx| {
+                    tx.exec(&migration.up, &[])?;
+                    tx.exec_typed(
+                        &format!("INSERT INTO {TRACKING_TABLE} (version, name) VALUES (?, ?)"),
+                        &[Value::Int(migration.version), Value::Text(migration.name.clone())],
+                    )?;
+                    Ok(())
+                })?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Reverts the last `n` applied migrations, running each one's `down`
+    /// SQL in reverse version order and removing its tracking row.
+    pub fn rollback(&self, n: usize) -> Result<()> {
+        self.with_lock(|| {
+            let mut applied = self.driver.applied_versions(TRACKING_TABLE)?;
+            applied.sort_unstable_by(|a, b| b.cmp(a));
+
+            for version in applied.into_iter().take(n) {
+                let migration = self
+                    .migrations
+                    .iter()
+                    .find(|m| m.version == version)
+                    .ok_or_else(|| {
+                        Error::Driver(format!("no migration registered for version {version}"))
+                    })?;
+
+                self.driver.transaction(|tx| {
+                    tx.exec(&migration.down, &[])?;
+                    tx.exec_typed(
+                        &format!("DELETE FROM {TRACKING_TABLE} WHERE version = ?"),
+                        &[Value::Int(version)],
+                    )?;
+                    Ok(())
+                })?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use super::*;
+
+    /// Backing store for [`FakeDriver`]/[`FakeTransaction`]. The lock table
+    /// gets its own slot (`Option<i64>`, the held expiry) rather than living
+    /// in `tables`, since its schema (an `id`-keyed row with a separate
+    /// `version`/expiry column) doesn't fit the "PK list" shape every other
+    /// table uses.
+    #[derive(Default)]
+    struct FakeState {
+        tables: RefCell<HashMap<String, Vec<i64>>>,
+        lock: RefCell<Option<i64>>,
+        fail_lock_release: std::cell::Cell<bool>,
+    }
+
+    /// A [`Driver`] that interprets the handful of literal SQL shapes
+    /// `Runner` generates (`CREATE TABLE`, `INSERT`, `UPDATE`, `DELETE`,
+    /// `SELECT version FROM`) against [`FakeState`], so `Runner`'s
+    /// version-selection and locking logic can be tested without a real
+    /// database. It doesn't understand arbitrary SQL — only the exact
+    /// statements this module writes.
+    #[derive(Default)]
+    struct FakeDriver {
+        state: Arc<FakeState>,
+    }
+
+    struct FakeTransaction {
+        state: Arc<FakeState>,
+    }
+
+    fn word_after<'a>(sql: &'a str, marker: &str) -> &'a str {
+        sql.split_once(marker)
+            .unwrap_or_else(|| panic!("expected `{marker}` in: {sql}"))
+            .1
+            .split_whitespace()
+            .next()
+            .unwrap_or_else(|| panic!("expected a table name after `{marker}` in: {sql}"))
+    }
+
+    fn values_tuple(sql: &str) -> Vec<&str> {
+        sql.split_once("VALUES")
+            .expect("expected a VALUES clause")
+            .1
+            .trim()
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .split(',')
+            .map(str::trim)
+            .collect()
+    }
+
+    /// Substitutes `?` placeholders with `args`, in order, the way a real
+    /// backend's client binds them — so `exec_typed`'s default
+    /// implementation (which forwards stringified [`Value`]s to `exec`'s
+    /// `&[&str]` args) round-trips through this fake the same way it would
+    /// against sqlite or mysql.
+    fn substitute_placeholders(sql: &str, args: &[&str]) -> String {
+        let mut out = String::with_capacity(sql.len());
+        let mut args = args.iter();
+        for c in sql.chars() {
+            if c == '?' {
+                out.push_str(args.next().expect("fewer args than `?` placeholders"));
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    fn run_sql(state: &FakeState, sql: &str) -> Result<()> {
+        if let Some(rest) = sql.strip_prefix("CREATE TABLE IF NOT EXISTS ") {
+            let table = rest.split_whitespace().next().unwrap();
+            if table != LOCK_TABLE {
+                state.tables.borrow_mut().entry(table.to_string()).or_default();
+            }
+            return Ok(());
+        }
+
+        if sql.starts_with("INSERT INTO") {
+            let table = word_after(sql, "INSERT INTO ");
+            let values = values_tuple(sql);
+
+            if table == LOCK_TABLE {
+                let expires_at: i64 = values[1]
+                    .parse()
+                    .expect("lock INSERT should carry a numeric expiry");
+                let mut lock = state.lock.borrow_mut();
+                if lock.is_some() {
+                    return Err(Error::Driver("duplicate primary key".into()));
+                }
+                *lock = Some(expires_at);
+                return Ok(());
+            }
+
+            let version: i64 = values[0]
+                .parse()
+                .expect("INSERT should carry a numeric version");
+            let mut tables = state.tables.borrow_mut();
+            let rows = tables.entry(table.to_string()).or_default();
+            if rows.contains(&version) {
+                return Err(Error::Driver("duplicate primary key".into()));
+            }
+            rows.push(version);
+            return Ok(());
+        }
+
+        if let Some(rest) = sql.strip_prefix("UPDATE ") {
+            let table = rest.split_whitespace().next().unwrap();
+            assert_eq!(table, LOCK_TABLE, "FakeDriver only expects UPDATEs against the lock table");
+
+            let expires_at: i64 = sql
+                .split_once("SET version = ")
+                .expect("UPDATE should set `version`")
+                .1
+                .split_once(" WHERE")
+                .map(|(v, _)| v)
+                .expect("UPDATE should carry a WHERE clause")
+                .trim()
+                .parse()
+                .expect("UPDATE should carry a numeric expiry");
+
+            *state.lock.borrow_mut() = Some(expires_at);
+            return Ok(());
+        }
+
+        if sql.starts_with("DELETE FROM") {
+            let table = word_after(sql, "DELETE FROM ");
+
+            if table == LOCK_TABLE {
+                if state.fail_lock_release.get() {
+                    return Err(Error::Driver("connection reset".into()));
+                }
+                *state.lock.borrow_mut() = None;
+                return Ok(());
+            }
+
+            let mut tables = state.tables.borrow_mut();
+            match sql.split_once("WHERE version = ") {
+                Some((_, rest)) => {
+                    let version: i64 = rest
+                        .trim()
+                        .parse()
+                        .expect("DELETE should carry a numeric version");
+                    tables
+                        .entry(table.to_string())
+                        .or_default()
+                        .retain(|v| *v != version);
+                }
+                // A migration's own `down` SQL, with no tracking `WHERE` clause.
+                None => {
+                    tables.entry(table.to_string()).or_default().clear();
+                }
+            }
+            return Ok(());
+        }
+
+        panic!("FakeDriver can't interpret: {sql}");
+    }
+
+    impl Driver for FakeDriver {
+        type Transaction = FakeTransaction;
+
+        fn exec(&self, sql: &str, args: &[&str]) -> Result<()> {
+            run_sql(&self.state, &substitute_placeholders(sql, args))
+        }
+
+        fn begin_transaction(&self) -> Result<Self::Transaction> {
+            Ok(FakeTransaction {
+                state: self.state.clone(),
+            })
+        }
+
+        fn applied_versions(&self, table: &str) -> Result<Vec<i64>> {
+            if table == LOCK_TABLE {
+                return Ok(self.state.lock.borrow().iter().copied().collect());
+            }
+            Ok(self
+                .state
+                .tables
+                .borrow()
+                .get(table)
+                .cloned()
+                .unwrap_or_default())
+        }
+    }
+
+    impl Transaction for FakeTransaction {
+        fn exec(&self, sql: &str, args: &[&str]) -> Result<()> {
+            run_sql(&self.state, &substitute_placeholders(sql, args))
+        }
+
+        fn begin(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn commit(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn rollback(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn savepoint(&self, _name: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn rollback_to(&self, _name: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn release(&self, _name: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn migration(version: i64) -> Migration {
+        Migration {
+            version,
+            name: format!("m{version}"),
+            up: format!("CREATE TABLE IF NOT EXISTS t{version} (id INTEGER)"),
+            down: format!("DELETE FROM t{version}"),
+        }
+    }
+
+    #[test]
+    fn migrate_applies_only_pending_versions_in_order() {
+        let driver = FakeDriver::default();
+        let runner = Runner::new(&driver, vec![migration(2), migration(1), migration(3)]);
+
+        runner.migrate().unwrap();
+
+        assert_eq!(
+            driver.applied_versions(TRACKING_TABLE).unwrap(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn migrate_is_idempotent() {
+        let driver = FakeDriver::default();
+        let runner = Runner::new(&driver, vec![migration(1), migration(2)]);
+
+        runner.migrate().unwrap();
+        runner.migrate().unwrap();
+
+        assert_eq!(driver.applied_versions(TRACKING_TABLE).unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn rollback_reverts_last_n_in_reverse_order() {
+        let driver = FakeDriver::default();
+        let runner = Runner::new(&driver, vec![migration(1), migration(2), migration(3)]);
+        runner.migrate().unwrap();
+
+        runner.rollback(2).unwrap();
+
+        assert_eq!(driver.applied_versions(TRACKING_TABLE).unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn with_lock_releases_the_lock_after_use() {
+        let driver = FakeDriver::default();
+        let runner = Runner::new(&driver, vec![migration(1)]);
+
+        runner.migrate().unwrap();
+        // A second call only succeeds if the first run released its lock.
+        runner.migrate().unwrap();
+
+        assert!(driver.applied_versions(LOCK_TABLE).unwrap().is_empty());
+    }
+
+    #[test]
+    fn held_lock_blocks_a_second_runner_instead_of_double_applying() {
+        let driver = FakeDriver::default();
+        let runner = Runner::new(&driver, vec![migration(1)]);
+
+        // Simulates another runner already holding the lock, well within its
+        // lease. The row's id is fixed, so this INSERT collides on the
+        // primary key regardless of the new runner's own computed expiry —
+        // unlike keying the row on the expiry itself, where two different
+        // expiries would both insert as distinct rows and double-apply.
+        *driver.state.lock.borrow_mut() = Some(now_unix() + 300);
+
+        assert!(runner.migrate().is_err());
+        assert!(driver.applied_versions(TRACKING_TABLE).unwrap().is_empty());
+    }
+
+    #[test]
+    fn with_lock_keeps_original_error_when_release_also_fails() {
+        let driver = FakeDriver::default();
+        // No migration is registered for version 1, so `rollback`'s closure
+        // fails inside `with_lock`, and the release DELETE that runs after
+        // it then fails too.
+        let runner = Runner::new(&driver, vec![]);
+        driver
+            .state
+            .tables
+            .borrow_mut()
+            .insert(TRACKING_TABLE.to_string(), vec![1]);
+        driver.state.fail_lock_release.set(true);
+
+        let result = runner.rollback(1);
+
+        match result {
+            Err(Error::LockReleaseFailed {
+                cause,
+                release_error,
+            }) => {
+                assert!(
+                    matches!(*cause, Error::Driver(ref m) if m.contains("no migration registered"))
+                );
+                assert!(matches!(*release_error, Error::Driver(ref m) if m == "connection reset"));
+            }
+            other => panic!("expected LockReleaseFailed, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn stale_lock_is_stolen_instead_of_blocking_forever() {
+        let driver = FakeDriver::default();
+        let runner = Runner::new(&driver, vec![migration(1)]).with_lock_lease_secs(-1);
+
+        // Simulates a runner that crashed while holding the lock: the
+        // expiry (`now - 1s`) is already in the past.
+        *driver.state.lock.borrow_mut() = Some(now_unix() - 1);
+
+        runner.migrate().unwrap();
+
+        assert_eq!(driver.applied_versions(TRACKING_TABLE).unwrap(), vec![1]);
+    }
+}
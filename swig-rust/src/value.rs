@@ -0,0 +1,131 @@
+/// A typed SQL parameter, used by `exec_typed` so callers don't have to
+/// pre-stringify integers, bytes, or nulls the way the original
+/// `exec(&self, sql, args: &[&str])` signature required.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+    Bool(bool),
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Value::Text(v.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Value::Text(v)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(v: i64) -> Self {
+        Value::Int(v)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Self {
+        Value::Float(v)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Self {
+        Value::Bool(v)
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(v: Vec<u8>) -> Self {
+        Value::Bytes(v)
+    }
+}
+
+impl Value {
+    /// Stringifies the value for backends that only implement the legacy
+    /// `exec(&self, sql, args: &[&str])` and fall back on `Driver`'s default
+    /// `exec_typed`. Lossy for `Bytes` and ambiguous for `Null`; backends
+    /// that can bind `Value` natively should override `exec_typed` instead
+    /// of relying on this.
+    pub fn to_bind_string(&self) -> String {
+        match self {
+            Value::Null => String::new(),
+            Value::Int(i) => i.to_string(),
+            Value::Float(f) => f.to_string(),
+            Value::Text(s) => s.clone(),
+            Value::Bytes(b) => String::from_utf8_lossy(b).into_owned(),
+            Value::Bool(b) => b.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl rusqlite::ToSql for Value {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        use rusqlite::types::{ToSqlOutput, Value as SqliteValue};
+
+        Ok(ToSqlOutput::Owned(match self {
+            Value::Null => SqliteValue::Null,
+            Value::Int(i) => SqliteValue::Integer(*i),
+            Value::Float(f) => SqliteValue::Real(*f),
+            Value::Text(s) => SqliteValue::Text(s.clone()),
+            Value::Bytes(b) => SqliteValue::Blob(b.clone()),
+            Value::Bool(b) => SqliteValue::Integer(*b as i64),
+        }))
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl From<Value> for mysql::Value {
+    fn from(v: Value) -> Self {
+        match v {
+            Value::Null => mysql::Value::NULL,
+            Value::Int(i) => mysql::Value::Int(i),
+            Value::Float(f) => mysql::Value::Double(f),
+            Value::Text(s) => mysql::Value::Bytes(s.into_bytes()),
+            Value::Bytes(b) => mysql::Value::Bytes(b),
+            Value::Bool(b) => mysql::Value::Int(b as i64),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_bind_string_covers_each_variant() {
+        assert_eq!(Value::Null.to_bind_string(), "");
+        assert_eq!(Value::Int(-7).to_bind_string(), "-7");
+        assert_eq!(Value::Float(1.5).to_bind_string(), "1.5");
+        assert_eq!(Value::Text("hi".into()).to_bind_string(), "hi");
+        assert_eq!(Value::Bytes(b"hi".to_vec()).to_bind_string(), "hi");
+        assert_eq!(Value::Bool(true).to_bind_string(), "true");
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn mysql_value_conversion_covers_each_variant() {
+        assert_eq!(mysql::Value::from(Value::Null), mysql::Value::NULL);
+        assert_eq!(mysql::Value::from(Value::Int(42)), mysql::Value::Int(42));
+        assert_eq!(
+            mysql::Value::from(Value::Float(1.5)),
+            mysql::Value::Double(1.5)
+        );
+        assert_eq!(
+            mysql::Value::from(Value::Text("hi".into())),
+            mysql::Value::Bytes(b"hi".to_vec())
+        );
+        assert_eq!(
+            mysql::Value::from(Value::Bytes(vec![1, 2, 3])),
+            mysql::Value::Bytes(vec![1, 2, 3])
+        );
+        assert_eq!(mysql::Value::from(Value::Bool(true)), mysql::Value::Int(1));
+    }
+}